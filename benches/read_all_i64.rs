@@ -0,0 +1,32 @@
+//! Compares `read_all_i64` against repeated `token::<i64>()` on 10^7 integers.
+//!
+//! Run with `cargo bench --bench read_all_i64`.
+
+use std::fmt::Write;
+use std::time::Instant;
+use turbo_input::Scanner;
+
+const N: usize = 10_000_000;
+
+fn main() {
+    let mut input = String::with_capacity(N * 12);
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    for i in 0..N {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let value = (state % 2_000_000_000) as i64 - 1_000_000_000;
+        let sep = if i % 10 == 9 { '\n' } else { ' ' };
+        write!(input, "{}{}", value, sep).unwrap();
+    }
+
+    let start = Instant::now();
+    let fast = Scanner::new(input.as_bytes()).read_all_i64();
+    println!("read_all_i64:   {:?}", start.elapsed());
+
+    let start = Instant::now();
+    let slow: Vec<i64> = Scanner::new(input.as_bytes()).vec(N);
+    println!("token::<i64>(): {:?}", start.elapsed());
+
+    assert_eq!(fast, slow);
+}
@@ -0,0 +1,97 @@
+//! Counts allocations made by `vec`, `matrix`, `read_all_i64` and
+//! `matrix_until_eof` on 10^6 integers, with and without `hint_total_tokens`.
+//!
+//! Run with `cargo bench --bench token_hint`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Write;
+use std::io::BufReader;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+use turbo_input::Scanner;
+
+const N: usize = 1_000_000;
+const COLS: usize = 4;
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Runs `f` and prints how long it took and how many allocations it made
+fn measure<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    let count = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!("{:<28} {:>9} allocations  {:?}", label, count, elapsed);
+    result
+}
+
+fn main() {
+    let mut input = String::with_capacity(N * 8);
+    for i in 0..N {
+        let sep = if i % COLS == COLS - 1 { '\n' } else { ' ' };
+        write!(input, "{}{}", i * 7 % 1_000_003, sep).unwrap();
+    }
+
+    // Read through an 8 KiB BufReader, as with stdin, so the line buffer
+    // grows in steps instead of taking the whole in-memory slice at once.
+    let wide = input.replace('\n', " ");
+    let plain: Vec<i64> = measure("vec (one line)", || {
+        Scanner::new(BufReader::new(wide.as_bytes())).vec(N)
+    });
+    let hinted: Vec<i64> = measure("vec (one line) + hint", || {
+        let mut scanner = Scanner::new(BufReader::new(wide.as_bytes()));
+        scanner.hint_total_tokens(N);
+        scanner.vec(N)
+    });
+    assert_eq!(plain, hinted);
+
+    let plain: Vec<Vec<i64>> = measure("matrix", || {
+        Scanner::new(input.as_bytes()).matrix(N / COLS, COLS)
+    });
+    let hinted: Vec<Vec<i64>> = measure("matrix + hint", || {
+        let mut scanner = Scanner::new(input.as_bytes());
+        scanner.hint_total_tokens(N);
+        scanner.matrix(N / COLS, COLS)
+    });
+    assert_eq!(plain, hinted);
+
+    let plain = measure("read_all_i64", || Scanner::new(input.as_bytes()).read_all_i64());
+    let hinted = measure("read_all_i64 + hint", || {
+        let mut scanner = Scanner::new(input.as_bytes());
+        scanner.hint_total_tokens(N);
+        scanner.read_all_i64()
+    });
+    assert_eq!(plain, hinted);
+
+    let plain: Vec<Vec<i64>> = measure("matrix_until_eof", || {
+        Scanner::new(input.as_bytes()).matrix_until_eof(COLS)
+    });
+    let hinted: Vec<Vec<i64>> = measure("matrix_until_eof + hint", || {
+        let mut scanner = Scanner::new(input.as_bytes());
+        scanner.hint_total_tokens(N);
+        scanner.matrix_until_eof(COLS)
+    });
+    assert_eq!(plain, hinted);
+}
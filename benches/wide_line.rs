@@ -0,0 +1,36 @@
+//! Reads 10^6 integers that all sit on a single line.
+//!
+//! The scanner keeps the line once and walks it with a byte cursor, so no
+//! per-token `String` is allocated. The second timing materializes every
+//! token up front, as a line-splitting tokenizer would, for comparison.
+//!
+//! Run with `cargo bench --bench wide_line`.
+
+use std::fmt::Write;
+use std::time::Instant;
+use turbo_input::Scanner;
+
+const N: usize = 1_000_000;
+
+fn main() {
+    let mut input = String::with_capacity(N * 8);
+    let mut state: u32 = 0x9e37_79b9;
+    for _ in 0..N {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        write!(input, "{} ", state % 1_000_000).unwrap();
+    }
+    input.push('\n');
+
+    let start = Instant::now();
+    let lazy: Vec<u32> = Scanner::new(input.as_bytes()).vec(N);
+    println!("Scanner::vec:          {:?}", start.elapsed());
+
+    let start = Instant::now();
+    let tokens: Vec<String> = input.split_whitespace().rev().map(String::from).collect();
+    let eager: Vec<u32> = tokens.into_iter().rev().map(|t| t.parse().unwrap()).collect();
+    println!("owned tokens per line: {:?}", start.elapsed());
+
+    assert_eq!(lazy, eager);
+}
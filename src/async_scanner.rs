@@ -0,0 +1,124 @@
+//! An asynchronous counterpart to [`Scanner`](crate::Scanner) for Tokio readers
+//!
+//! Enabled with the `async` feature. The API is deliberately small: it reads
+//! whitespace-separated tokens from any [`AsyncBufRead`], which is enough to
+//! drive contest-style logic from a socket inside a Tokio service.
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+use crate::{or_panic, ScanError};
+
+/// A whitespace-token scanner over an asynchronous reader
+///
+/// # Examples
+///
+/// ```
+/// use turbo_input::AsyncScanner;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let input = "3\n1 2 3\n";
+/// let mut scanner = AsyncScanner::new(input.as_bytes());
+///
+/// let n: usize = scanner.token().await;
+/// let values: Vec<i32> = scanner.vec(n).await;
+/// assert_eq!(values, vec![1, 2, 3]);
+/// # });
+/// ```
+pub struct AsyncScanner<R> {
+    reader: R,
+    line: String,
+    pos: usize,
+}
+
+impl<R: AsyncBufRead + Unpin> AsyncScanner<R> {
+    /// Creates a new AsyncScanner from any type that implements AsyncBufRead
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Any type implementing AsyncBufRead (e.g., `tokio::io::BufReader`)
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            pos: 0,
+        }
+    }
+
+    /// Reads the next token and parses it to the specified type
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input ends or the token cannot be parsed to type T.
+    pub async fn token<T: std::str::FromStr>(&mut self) -> T {
+        or_panic(self.try_token().await)
+    }
+
+    /// Reads the next token and parses it, returning an error instead of panicking
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScanError::Eof`] if the input is exhausted,
+    /// [`ScanError::Parse`] if the token is not a valid `T`, and
+    /// [`ScanError::Io`] if reading fails.
+    pub async fn try_token<T: std::str::FromStr>(&mut self) -> Result<T, ScanError> {
+        loop {
+            let rest = &self.line[self.pos..];
+            let skipped = rest.len() - rest.trim_start().len();
+            self.pos += skipped;
+            if self.pos < self.line.len() {
+                break;
+            }
+            self.line.clear();
+            self.pos = 0;
+            if self.reader.read_line(&mut self.line).await? == 0 {
+                return Err(ScanError::Eof);
+            }
+        }
+        let start = self.pos;
+        let rest = &self.line[start..];
+        let end = rest.find(char::is_whitespace).map_or(self.line.len(), |i| start + i);
+        self.pos = end;
+        let token = &self.line[start..end];
+        token.parse().map_err(|_| ScanError::Parse {
+            token: token.to_string(),
+            type_name: std::any::type_name::<T>(),
+        })
+    }
+
+    /// Reads n tokens and returns them as a vector
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input ends early or any token cannot be parsed.
+    pub async fn vec<T: std::str::FromStr>(&mut self, n: usize) -> Vec<T> {
+        let mut values = Vec::with_capacity(n);
+        for _ in 0..n {
+            values.push(self.token().await);
+        }
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncWriteExt, BufReader};
+
+    #[tokio::test]
+    async fn test_async_scanner_over_pipe() {
+        let (mut writer, reader) = tokio::io::duplex(16);
+        let feeder = tokio::spawn(async move {
+            writer.write_all(b"4\n10 20\n30 40\nend").await.unwrap();
+        });
+
+        let mut scanner = AsyncScanner::new(BufReader::new(reader));
+        let n: usize = scanner.token().await;
+        let values: Vec<u32> = scanner.vec(n).await;
+        let word: String = scanner.token().await;
+        feeder.await.unwrap();
+
+        assert_eq!(values, vec![10, 20, 30, 40]);
+        assert_eq!(word, "end");
+        assert!(matches!(scanner.try_token::<u32>().await, Err(ScanError::Eof)));
+    }
+}
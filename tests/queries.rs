@@ -0,0 +1,52 @@
+use std::io::Write;
+use turbo_input::{Printer, Scanner};
+
+/// Range-sum queries answered from prefix sums, read from and written to
+/// in-memory buffers.
+#[test]
+fn answer_queries_over_in_memory_buffers() {
+    let input = "5\n3 1 4 1 5\n3\n1 5\n2 3\n4 4\n";
+    let mut scanner = Scanner::new(input.as_bytes());
+    let mut buf = Vec::new();
+    let mut out = Printer::new(&mut buf);
+
+    let n: usize = scanner.token();
+    let mut prefix = vec![0i64; n + 1];
+    for i in 1..=n {
+        prefix[i] = prefix[i - 1] + scanner.token::<i64>();
+    }
+    let q: usize = scanner.token();
+    scanner.answer_queries(q, &mut out, |scan| {
+        let (l, r): (usize, usize) = (scan.token(), scan.token());
+        (prefix[r] - prefix[l - 1]).to_string()
+    });
+    scanner.assert_eof();
+    drop(out);
+
+    assert_eq!(String::from_utf8(buf).unwrap(), "14\n5\n1\n");
+}
+
+/// Nothing reaches the writer until the batch is complete.
+#[test]
+fn answer_queries_flushes_only_at_the_end() {
+    struct Recorder<'a>(&'a mut Vec<usize>);
+
+    impl Write for Recorder<'_> {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.push(buf.len());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut writes = Vec::new();
+    let mut out = Printer::new(Recorder(&mut writes));
+    let mut scanner = Scanner::new("a b c".as_bytes());
+    scanner.answer_queries(3, &mut out, |scan| scan.string());
+    drop(out);
+
+    assert_eq!(writes, vec![6]);
+}
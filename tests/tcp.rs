@@ -0,0 +1,40 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use turbo_input::Scanner;
+
+/// A mock judge that asks three questions and checks each answer before
+/// sending the next one, so the scanner must not read ahead of the protocol.
+#[test]
+fn interactive_round_trip_over_loopback() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let judge = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut out = stream.try_clone().unwrap();
+        let mut answers = BufReader::new(stream);
+        for question in [3, 10, -7] {
+            writeln!(out, "{} {}", question, question + 1).unwrap();
+            out.flush().unwrap();
+
+            let mut answer = String::new();
+            answers.read_line(&mut answer).unwrap();
+            assert_eq!(answer.trim(), (2 * question + 1).to_string());
+        }
+        writeln!(out, "done").unwrap();
+    });
+
+    let stream = TcpStream::connect(addr).unwrap();
+    let mut out = stream.try_clone().unwrap();
+    let mut scanner = Scanner::from_tcp(stream);
+    for _ in 0..3 {
+        let a: i64 = scanner.token();
+        let b: i64 = scanner.token();
+        writeln!(out, "{}", a + b).unwrap();
+        out.flush().unwrap();
+    }
+    assert_eq!(scanner.string(), "done");
+
+    judge.join().unwrap();
+}
@@ -0,0 +1,37 @@
+use proptest::prelude::*;
+use std::io::BufReader;
+use turbo_input::Scanner;
+
+/// Builds input from words joined by runs of mixed whitespace, including
+/// line breaks, CRLF endings and a few non-ASCII separators.
+fn whitespace_input() -> impl Strategy<Value = String> {
+    let word = "[a-zA-Z0-9_.+\\-é中]{1,8}";
+    let gap = prop::collection::vec(
+        prop::sample::select(vec![" ", "\t", "\n", "\r\n", "\u{a0}", "\u{3000}"]),
+        1..4,
+    )
+    .prop_map(|parts| parts.concat());
+    (gap.clone(), prop::collection::vec((word, gap), 0..40)).prop_map(|(lead, pairs)| {
+        let mut input = lead;
+        for (word, gap) in pairs {
+            input.push_str(&word);
+            input.push_str(&gap);
+        }
+        input
+    })
+}
+
+proptest! {
+    /// `token()` must yield exactly the words of `split_whitespace`, in order,
+    /// however the input is broken into lines and buffer fills.
+    #[test]
+    fn tokens_match_split_whitespace(input in whitespace_input(), capacity in 1usize..16) {
+        let expected: Vec<&str> = input.split_whitespace().collect();
+        let reader = BufReader::with_capacity(capacity, input.as_bytes());
+        let mut scanner = Scanner::new(reader);
+
+        let tokens: Vec<String> = (0..expected.len()).map(|_| scanner.token()).collect();
+        prop_assert_eq!(tokens, expected);
+        prop_assert!(scanner.try_token::<String>().is_err());
+    }
+}